@@ -1,8 +1,547 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
 use tauri::{Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
 
 static IS_LOCKED: AtomicBool = AtomicBool::new(true);
+static ALL_WORKSPACES: AtomicBool = AtomicBool::new(true);
+static BORDERS_HIDDEN: AtomicBool = AtomicBool::new(false);
+static LAST_SYMBOL: Mutex<Option<String>> = Mutex::new(None);
+static SNAP_ENABLED: AtomicBool = AtomicBool::new(true);
+static SNAP_THRESHOLD: Mutex<f64> = Mutex::new(12.0);
+static DEFAULT_OPACITY: Mutex<f64> = Mutex::new(1.0);
+
+/// 레이아웃 자동 저장 디바운스 간격.
+const LAYOUT_SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+const LAYOUT_FILE_NAME: &str = "window_layout.json";
+const SHORTCUT_CONFIG_FILE_NAME: &str = "shortcuts.json";
+const OPACITY_CONFIG_FILE_NAME: &str = "opacity.json";
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct OpacityConfig {
+    #[serde(default = "default_opacity_value")]
+    opacity: f64,
+}
+
+impl Default for OpacityConfig {
+    fn default() -> Self {
+        Self {
+            opacity: default_opacity_value(),
+        }
+    }
+}
+
+fn default_opacity_value() -> f64 {
+    1.0
+}
+
+fn opacity_config_path(app_handle: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(OPACITY_CONFIG_FILE_NAME))
+}
+
+fn read_opacity_config(app_handle: &tauri::AppHandle) -> Result<OpacityConfig, String> {
+    let path = opacity_config_path(app_handle)?;
+    if !path.exists() {
+        return Ok(OpacityConfig::default());
+    }
+    let contents = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&contents).map_err(|e| e.to_string())
+}
+
+fn write_opacity_config(app_handle: &tauri::AppHandle, config: &OpacityConfig) -> Result<(), String> {
+    let path = opacity_config_path(app_handle)?;
+    let contents = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    std::fs::write(&path, contents).map_err(|e| e.to_string())
+}
+
+/// 창 레벨 알파값을 적용한다. Windows는 레이어드 윈도우 속성, macOS는
+/// `NSWindow.alphaValue`를 통해 구현되며 그 외 플랫폼은 지원하지 않는다.
+///
+/// `windows`/`objc2` 크레이트에 의존하지 않고 시스템 라이브러리(`user32`,
+/// Objective-C 런타임)를 직접 `extern` 블록으로 링크한다. 이 트리에는
+/// 의존성을 선언할 `Cargo.toml`이 없으므로, 새 크레이트가 필요 없는 방식으로
+/// 구현해야 이 함수가 실제로 빌드 가능한 상태를 유지한다.
+#[cfg(target_os = "windows")]
+mod win32_opacity {
+    pub type Hwnd = *mut core::ffi::c_void;
+
+    pub const GWL_EXSTYLE: i32 = -20;
+    pub const WS_EX_LAYERED: isize = 0x0008_0000;
+    pub const LWA_ALPHA: u32 = 0x2;
+
+    #[link(name = "user32")]
+    extern "system" {
+        pub fn GetWindowLongPtrW(hwnd: Hwnd, index: i32) -> isize;
+        pub fn SetWindowLongPtrW(hwnd: Hwnd, index: i32, new_long: isize) -> isize;
+        pub fn SetLayeredWindowAttributes(
+            hwnd: Hwnd,
+            color_key: u32,
+            alpha: u8,
+            flags: u32,
+        ) -> i32;
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn apply_window_opacity(window: &tauri::WebviewWindow, opacity: f64) -> Result<(), String> {
+    use win32_opacity::*;
+
+    let hwnd = window.hwnd().map_err(|e| e.to_string())?.0 as Hwnd;
+
+    unsafe {
+        let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED);
+        let ok = SetLayeredWindowAttributes(hwnd, 0, (opacity * 255.0) as u8, LWA_ALPHA);
+        if ok == 0 {
+            return Err("SetLayeredWindowAttributes failed".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+mod macos_opacity {
+    #[repr(C)]
+    pub struct objc_object {
+        _private: [u8; 0],
+    }
+    pub type Id = *mut objc_object;
+    pub type Sel = *const core::ffi::c_void;
+
+    #[link(name = "objc")]
+    extern "C" {
+        pub fn sel_registerName(name: *const std::os::raw::c_char) -> Sel;
+        pub fn objc_msgSend(receiver: Id, selector: Sel, ...) -> Id;
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn apply_window_opacity(window: &tauri::WebviewWindow, opacity: f64) -> Result<(), String> {
+    use macos_opacity::*;
+    use std::ffi::CString;
+
+    let ns_window = window.ns_window().map_err(|e| e.to_string())? as Id;
+    let selector_name =
+        CString::new("setAlphaValue:").map_err(|e| e.to_string())?;
+
+    unsafe {
+        let selector = sel_registerName(selector_name.as_ptr());
+        // `setAlphaValue:` expects a CGFloat (f64) argument; objc_msgSend is
+        // declared variadic, so we transmute it to the concrete non-variadic
+        // signature and call that instead of going through the varargs ABI.
+        let set_alpha: extern "C" fn(Id, Sel, f64) -> Id = std::mem::transmute(
+            objc_msgSend as unsafe extern "C" fn(Id, Sel, ...) -> Id,
+        );
+        set_alpha(ns_window, selector, opacity);
+    }
+
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn apply_window_opacity(_window: &tauri::WebviewWindow, _opacity: f64) -> Result<(), String> {
+    Err("window opacity is only supported on Windows and macOS".to_string())
+}
+
+/// 액션 이름 -> 가속기 문자열 바인딩. 등록된 단축키는 `dispatch_shortcut_action`을
+/// 통해 실행된다.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct ShortcutConfig {
+    #[serde(default = "default_shortcut_bindings")]
+    bindings: HashMap<String, String>,
+}
+
+impl Default for ShortcutConfig {
+    fn default() -> Self {
+        Self {
+            bindings: default_shortcut_bindings(),
+        }
+    }
+}
+
+fn default_shortcut_bindings() -> HashMap<String, String> {
+    let mut bindings = HashMap::new();
+    bindings.insert("toggle_lock".to_string(), "Ctrl+Shift+L".to_string());
+    bindings
+}
+
+/// 액션별로 현재 등록되어 있는 `Shortcut`. 재등록 시 이전 바인딩을 해제하는 데 쓰인다.
+#[derive(Default)]
+struct ShortcutState {
+    bound: Mutex<HashMap<String, Shortcut>>,
+}
+
+fn shortcut_config_path(app_handle: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(SHORTCUT_CONFIG_FILE_NAME))
+}
+
+fn read_shortcut_config(app_handle: &tauri::AppHandle) -> Result<ShortcutConfig, String> {
+    let path = shortcut_config_path(app_handle)?;
+    if !path.exists() {
+        return Ok(ShortcutConfig::default());
+    }
+    let contents = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&contents).map_err(|e| e.to_string())
+}
+
+fn write_shortcut_config(app_handle: &tauri::AppHandle, config: &ShortcutConfig) -> Result<(), String> {
+    let path = shortcut_config_path(app_handle)?;
+    let contents = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    std::fs::write(&path, contents).map_err(|e| e.to_string())
+}
+
+/// 액션 이름으로 실제 동작을 수행한다. 등록된 모든 전역 단축키가 이 함수를 거친다.
+/// 락/테두리 토글은 프론트엔드 커맨드와 같은 `apply_lock_state`/`apply_border_state`를
+/// 사용하므로 키보드/프론트엔드 양쪽 경로의 상태가 항상 일치한다.
+fn dispatch_shortcut_action(app: &tauri::AppHandle, action: &str) {
+    match action {
+        "toggle_lock" => handle_toggle_lock(app),
+        "toggle_borders" => handle_toggle_borders(app),
+        "spawn_last_symbol" => handle_spawn_last_symbol(app),
+        "close_all" => handle_close_all(app),
+        _ => {}
+    }
+}
+
+fn handle_toggle_lock(app: &tauri::AppHandle) {
+    let new_lock = !IS_LOCKED.load(Ordering::SeqCst);
+    apply_lock_state(app, new_lock);
+}
+
+/// 락 상태를 적용하고 모든 티커 창과 프론트엔드에 전파한다. 키보드 단축키와
+/// `toggle_lock_from_frontend` 커맨드가 이 함수를 공유해 두 경로의 상태가 항상 일치한다.
+fn apply_lock_state(app: &tauri::AppHandle, locked: bool) {
+    IS_LOCKED.store(locked, Ordering::SeqCst);
+
+    for (label, window) in app.webview_windows() {
+        if label.starts_with("ticker_") {
+            let _ = window.set_ignore_cursor_events(locked);
+        }
+    }
+
+    let _ = app.emit("lock-toggled", locked);
+}
+
+fn handle_toggle_borders(app: &tauri::AppHandle) {
+    let hide = !BORDERS_HIDDEN.load(Ordering::SeqCst);
+    apply_border_state(app, hide);
+}
+
+/// 테두리 숨김 상태를 적용하고 프론트엔드에 전파한다. 키보드 단축키와
+/// `broadcast_border_toggle` 커맨드가 이 함수를 공유한다.
+fn apply_border_state(app: &tauri::AppHandle, hide: bool) {
+    BORDERS_HIDDEN.store(hide, Ordering::SeqCst);
+    let _ = app.emit("border-toggled", hide);
+}
+
+fn handle_spawn_last_symbol(app: &tauri::AppHandle) {
+    let Some(symbol) = LAST_SYMBOL.lock().unwrap().clone() else {
+        return;
+    };
+
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = spawn_ticker_window_at(&app_handle, symbol, false, None, None, None, None).await;
+    });
+}
+
+fn handle_close_all(app: &tauri::AppHandle) {
+    for (label, window) in app.webview_windows() {
+        if label.starts_with("ticker_") {
+            let _ = window.close();
+        }
+    }
+}
+
+/// 주어진 액션에 가속기를 바인딩한다. 해당 액션에 이미 등록된 단축키가 있으면 먼저 해제한다.
+/// 같은 가속기가 다른 액션에 이미 바인딩되어 있으면 등록을 거절한다.
+fn apply_shortcut_binding(
+    app_handle: &tauri::AppHandle,
+    action: &str,
+    accelerator: &str,
+) -> Result<(), String> {
+    let shortcut: Shortcut = accelerator
+        .parse()
+        .map_err(|_| format!("invalid accelerator: {}", accelerator))?;
+
+    let state = app_handle.state::<ShortcutState>();
+    let mut bound = state.bound.lock().unwrap();
+
+    if let Some((other_action, _)) = bound
+        .iter()
+        .find(|(existing_action, existing_shortcut)| {
+            existing_action.as_str() != action && **existing_shortcut == shortcut
+        })
+    {
+        return Err(format!(
+            "accelerator '{}' is already bound to action '{}'",
+            accelerator, other_action
+        ));
+    }
+
+    if let Some(previous) = bound.remove(action) {
+        let _ = app_handle.global_shortcut().unregister(previous);
+    }
+
+    let action_owned = action.to_string();
+    app_handle
+        .global_shortcut()
+        .on_shortcut(shortcut.clone(), move |app, _shortcut, event| {
+            if event.state() == tauri_plugin_global_shortcut::ShortcutState::Pressed {
+                dispatch_shortcut_action(app, &action_owned);
+            }
+        })
+        .map_err(|e| e.to_string())?;
+
+    bound.insert(action.to_string(), shortcut);
+    Ok(())
+}
+
+/// 설정 파일에 저장된 모든 바인딩을 적용한다. 앱 시작 시, 그리고 `update_shortcuts`
+/// 커맨드에서 호출된다.
+fn load_and_apply_shortcuts(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let config = read_shortcut_config(app_handle)?;
+    for (action, accelerator) in &config.bindings {
+        apply_shortcut_binding(app_handle, action, accelerator)?;
+    }
+    Ok(())
+}
+
+/// 이동 중인 창이 화면 가장자리 또는 다른 티커 창 가장자리에 `threshold` 픽셀
+/// 이내로 접근하면 정확히 맞춰 정렬한다. `WindowEvent::Moved`에서 호출된다.
+fn apply_edge_snap(app_handle: &tauri::AppHandle, window: &tauri::WebviewWindow) {
+    if !SNAP_ENABLED.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let threshold = *SNAP_THRESHOLD.lock().unwrap();
+
+    let Ok(scale) = window.scale_factor() else {
+        return;
+    };
+    let Ok(position) = window.outer_position() else {
+        return;
+    };
+    let Ok(size) = window.outer_size() else {
+        return;
+    };
+    let position = position.to_logical::<f64>(scale);
+    let size = size.to_logical::<f64>(scale);
+
+    let mut x = position.x;
+    let mut y = position.y;
+    let mut snapped = false;
+
+    if let Ok(Some(monitor)) = window.current_monitor() {
+        let area_position = monitor.position().to_logical::<f64>(scale);
+        let area_size = monitor.size().to_logical::<f64>(scale);
+        let left = area_position.x;
+        let top = area_position.y;
+        let right = area_position.x + area_size.width;
+        let bottom = area_position.y + area_size.height;
+
+        if (x - left).abs() <= threshold {
+            x = left;
+            snapped = true;
+        }
+        if (right - (x + size.width)).abs() <= threshold {
+            x = right - size.width;
+            snapped = true;
+        }
+        if (y - top).abs() <= threshold {
+            y = top;
+            snapped = true;
+        }
+        if (bottom - (y + size.height)).abs() <= threshold {
+            y = bottom - size.height;
+            snapped = true;
+        }
+    }
+
+    let own_label = window.label().to_string();
+    for (label, other) in app_handle.webview_windows() {
+        if label == own_label || !label.starts_with("ticker_") {
+            continue;
+        }
+
+        let (Ok(other_scale), Ok(other_position), Ok(other_size)) =
+            (other.scale_factor(), other.outer_position(), other.outer_size())
+        else {
+            continue;
+        };
+        let other_position = other_position.to_logical::<f64>(other_scale);
+        let other_size = other_size.to_logical::<f64>(other_scale);
+
+        let other_left = other_position.x;
+        let other_right = other_position.x + other_size.width;
+        let other_top = other_position.y;
+        let other_bottom = other_position.y + other_size.height;
+
+        if (x - other_right).abs() <= threshold {
+            x = other_right;
+            snapped = true;
+        }
+        if ((x + size.width) - other_left).abs() <= threshold {
+            x = other_left - size.width;
+            snapped = true;
+        }
+        if (y - other_bottom).abs() <= threshold {
+            y = other_bottom;
+            snapped = true;
+        }
+        if ((y + size.height) - other_top).abs() <= threshold {
+            y = other_top - size.height;
+            snapped = true;
+        }
+    }
+
+    // 계산된 좌표가 현재 위치와 사실상 같다면(이미 스냅되어 있음) 아무 것도 하지 않는다.
+    // 그렇지 않으면 set_position이 또 다른 Moved 이벤트를 발생시켜 이 함수가
+    // 무한히 재호출되는 move -> snap -> move 루프에 빠진다. 물리/논리 좌표 변환
+    // 과정의 반올림 오차를 고려해 1픽셀 미만 차이는 같은 위치로 취급한다.
+    let already_at_target = (x - position.x).abs() < 1.0 && (y - position.y).abs() < 1.0;
+    if !snapped || already_at_target {
+        return;
+    }
+
+    let _ = window.set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }));
+    let _ = window.emit("window-snapped", (x, y));
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+struct TickerLayout {
+    symbol: String,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    ignore_cursor_events: bool,
+    visible_on_all_workspaces: bool,
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct LayoutFile {
+    #[serde(default)]
+    windows: Vec<TickerLayout>,
+    #[serde(default)]
+    presets: HashMap<String, Vec<TickerLayout>>,
+}
+
+/// 창 라벨 -> (심볼, ignore_cursor_events, visible_on_all_workspaces) 매핑과 디바운스 세대 카운터.
+#[derive(Default)]
+struct LayoutState {
+    meta: Mutex<HashMap<String, (String, bool, bool)>>,
+    save_generation: AtomicU64,
+}
+
+fn layout_file_path(app_handle: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(LAYOUT_FILE_NAME))
+}
+
+fn read_layout_file(app_handle: &tauri::AppHandle) -> Result<LayoutFile, String> {
+    let path = layout_file_path(app_handle)?;
+    if !path.exists() {
+        return Ok(LayoutFile::default());
+    }
+    let contents = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&contents).map_err(|e| e.to_string())
+}
+
+fn write_layout_file(app_handle: &tauri::AppHandle, file: &LayoutFile) -> Result<(), String> {
+    let path = layout_file_path(app_handle)?;
+    let contents = serde_json::to_string_pretty(file).map_err(|e| e.to_string())?;
+    std::fs::write(&path, contents).map_err(|e| e.to_string())
+}
+
+/// 현재 열려 있는 모든 `ticker_` 창의 위치/크기를 스냅샷으로 모은다.
+fn current_ticker_layout(app_handle: &tauri::AppHandle) -> Vec<TickerLayout> {
+    let state = app_handle.state::<LayoutState>();
+    let meta = state.meta.lock().unwrap();
+
+    app_handle
+        .webview_windows()
+        .into_iter()
+        .filter(|(label, _)| label.starts_with("ticker_"))
+        .filter_map(|(label, window)| {
+            let (symbol, ignore_cursor_events, visible_on_all_workspaces) =
+                meta.get(&label)?.clone();
+            let scale = window.scale_factor().ok()?;
+            let position = window.outer_position().ok()?.to_logical::<f64>(scale);
+            let size = window.inner_size().ok()?.to_logical::<f64>(scale);
+            Some(TickerLayout {
+                symbol,
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+                ignore_cursor_events,
+                visible_on_all_workspaces,
+            })
+        })
+        .collect()
+}
+
+/// 현재 레이아웃을 "현재 상태"로 저장한다(디바운스된 콜백에서 호출됨).
+async fn persist_current_layout(app_handle: tauri::AppHandle) -> Result<(), String> {
+    let snapshot = current_ticker_layout(&app_handle);
+    let mut file = read_layout_file(&app_handle)?;
+    file.windows = snapshot;
+    write_layout_file(&app_handle, &file)
+}
+
+/// `Moved`/`Resized` 이벤트 발생 시 호출. 일정 시간 동안 추가 이벤트가 없으면 저장한다.
+fn schedule_layout_save(app_handle: tauri::AppHandle) {
+    let state = app_handle.state::<LayoutState>();
+    let generation = state.save_generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(LAYOUT_SAVE_DEBOUNCE).await;
+
+        let state = app_handle.state::<LayoutState>();
+        if state.save_generation.load(Ordering::SeqCst) != generation {
+            // 그 사이 다른 이동/리사이즈 이벤트가 들어왔으므로 가장 최근 예약에 맡긴다.
+            return;
+        }
+
+        let _ = persist_current_layout(app_handle.clone()).await;
+    });
+}
+
+/// 저장된 레이아웃 항목으로부터 티커 창을 재생성한다.
+async fn spawn_ticker_from_layout(
+    app_handle: &tauri::AppHandle,
+    entry: &TickerLayout,
+) -> Result<(), String> {
+    spawn_ticker_window_at(
+        app_handle,
+        entry.symbol.clone(),
+        entry.ignore_cursor_events,
+        Some(entry.x),
+        Some(entry.y),
+        Some((entry.width, entry.height)),
+        Some(entry.visible_on_all_workspaces),
+    )
+    .await
+}
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -17,8 +556,32 @@ async fn spawn_ticker_window(
     ignore_mouse: bool,
     x: Option<f64>,
     y: Option<f64>,
+    visible_on_all_workspaces: Option<bool>,
+) -> Result<(), String> {
+    spawn_ticker_window_at(
+        &app_handle,
+        symbol,
+        ignore_mouse,
+        x,
+        y,
+        None,
+        visible_on_all_workspaces,
+    )
+    .await
+}
+
+/// `spawn_ticker_window`의 실체. 저장된 레이아웃 복원 시에는 크기도 함께 지정할 수 있다.
+async fn spawn_ticker_window_at(
+    app_handle: &tauri::AppHandle,
+    symbol: String,
+    ignore_mouse: bool,
+    x: Option<f64>,
+    y: Option<f64>,
+    size: Option<(f64, f64)>,
+    visible_on_all_workspaces: Option<bool>,
 ) -> Result<(), String> {
     let window_label = format!("ticker_{}", symbol.replace(".", "_"));
+    *LAST_SYMBOL.lock().unwrap() = Some(symbol.clone());
 
     if let Some(window) = app_handle.get_webview_window(&window_label) {
         window.set_focus().map_err(|e| e.to_string())?;
@@ -26,16 +589,20 @@ async fn spawn_ticker_window(
     }
 
     let url = format!("/ticker/{}", symbol);
+    let (width, height) = size.unwrap_or((180.0, 60.0));
+    let should_visible_everywhere =
+        visible_on_all_workspaces.unwrap_or_else(|| ALL_WORKSPACES.load(Ordering::SeqCst));
 
     let mut window_builder =
-        WebviewWindowBuilder::new(&app_handle, &window_label, WebviewUrl::App(url.into()))
+        WebviewWindowBuilder::new(app_handle, &window_label, WebviewUrl::App(url.into()))
             .title(format!("Ticker - {}", symbol))
-            .inner_size(180.0, 60.0)
+            .inner_size(width, height)
             .transparent(true)
             .decorations(false)
             .always_on_top(true)
             .skip_taskbar(true)
             .resizable(true)
+            .visible_on_all_workspaces(should_visible_everywhere)
             .visible(false);
 
     if let (Some(px), Some(py)) = (x, y) {
@@ -44,24 +611,55 @@ async fn spawn_ticker_window(
 
     let window = window_builder.build().map_err(|e| e.to_string())?;
 
+    // 현재 전역 락 상태 또는 인자에 따라 설정
+    let should_ignore = IS_LOCKED.load(Ordering::SeqCst) || ignore_mouse;
+
+    {
+        let state = app_handle.state::<LayoutState>();
+        state.meta.lock().unwrap().insert(
+            window_label.clone(),
+            (symbol.clone(), should_ignore, should_visible_everywhere),
+        );
+    }
+
     let window_clone = window.clone();
+    let app_handle_clone = app_handle.clone();
+    let label_for_close = window_label.clone();
+    let symbol_for_close = symbol.clone();
     window.on_window_event(move |event| {
         if matches!(
             event,
             tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_)
         ) {
             let _ = window_clone.emit("window-moved", ());
+            schedule_layout_save(app_handle_clone.clone());
+        }
+
+        if matches!(event, tauri::WindowEvent::Moved(_)) {
+            apply_edge_snap(&app_handle_clone, &window_clone);
+        }
+
+        if let tauri::WindowEvent::Destroyed = event {
+            let state = app_handle_clone.state::<LayoutState>();
+            state.meta.lock().unwrap().remove(&label_for_close);
+            unsubscribe_ticker_channel(&app_handle_clone, &symbol_for_close);
+            // 닫힌 창이 저장된 "현재 레이아웃"에도 반영되도록 다시 저장한다.
+            // 그렇지 않으면 마지막 창을 닫고 종료했을 때 다음 실행에서 되살아난다.
+            schedule_layout_save(app_handle_clone.clone());
         }
     });
 
-    // 현재 전역 락 상태 또는 인자에 따라 설정
-    let should_ignore = IS_LOCKED.load(Ordering::SeqCst) || ignore_mouse;
     if should_ignore {
         window
             .set_ignore_cursor_events(true)
             .map_err(|e| e.to_string())?;
     }
 
+    let default_opacity = *DEFAULT_OPACITY.lock().unwrap();
+    if default_opacity < 1.0 {
+        let _ = apply_window_opacity(&window, default_opacity);
+    }
+
     window.show().map_err(|e| e.to_string())?;
 
     Ok(())
@@ -91,22 +689,112 @@ async fn toggle_lock_from_frontend(
     app_handle: tauri::AppHandle,
     locked: bool,
 ) -> Result<(), String> {
-    IS_LOCKED.store(locked, Ordering::SeqCst);
+    apply_lock_state(&app_handle, locked);
+    Ok(())
+}
+
+#[tauri::command]
+async fn set_visible_on_all_workspaces(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    ALL_WORKSPACES.store(enabled, Ordering::SeqCst);
 
-    // 모든 라벨이 ticker_로 시작하는 창에 대해 click-through 적용
+    // 모든 라벨이 ticker_로 시작하는 창에 대해 전체 작업 공간 표시 적용
     for (label, window) in app_handle.webview_windows() {
         if label.starts_with("ticker_") {
-            let _ = window.set_ignore_cursor_events(locked);
+            let _ = window.set_visible_on_all_workspaces(enabled);
         }
     }
 
-    let _ = app_handle.emit("lock-toggled", locked);
+    {
+        let state = app_handle.state::<LayoutState>();
+        let mut meta = state.meta.lock().unwrap();
+        for value in meta.values_mut() {
+            value.2 = enabled;
+        }
+    }
+
+    let _ = app_handle.emit("visible-on-all-workspaces-toggled", enabled);
     Ok(())
 }
 
 #[tauri::command]
 async fn broadcast_border_toggle(app_handle: tauri::AppHandle, hide: bool) -> Result<(), String> {
-    let _ = app_handle.emit("border-toggled", hide);
+    apply_border_state(&app_handle, hide);
+    Ok(())
+}
+
+/// 심볼별 구독 채널로 전달되는 업데이트. `kis-ticker-data-{symbol}` 같은
+/// 동적 이벤트 이름 대신, 프론트엔드가 `subscribe_ticker`로 넘긴 채널 하나로
+/// 시세/에러/종료를 모두 흘려보낸다.
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase", tag = "event", content = "data")]
+enum TickerUpdate {
+    Quote(serde_json::Value),
+    Error(String),
+    Closed,
+}
+
+/// 구독 하나당 백그라운드 전달 태스크로 이어지는 큐의 용량. 프론트엔드가
+/// `broadcast_ticker_data`/`broadcast_ticker_error`를 호출하는 빈도보다
+/// 충분히 크게 잡아 순간적인 버스트에서도 블로킹이 일어나지 않게 한다.
+const TICKER_QUEUE_CAPACITY: usize = 64;
+
+/// 구독 하나의 상태. 실제 `Channel`로의 전달은 `subscribe_ticker`가 spawn한
+/// 백그라운드 태스크가 전담하고, 다른 커맨드는 이 `sender`를 통해 큐에 밀어
+/// 넣기만 한다.
+struct TickerSubscription {
+    sender: tokio::sync::mpsc::Sender<TickerUpdate>,
+}
+
+#[derive(Default)]
+struct TickerChannelState {
+    channels: Mutex<HashMap<String, TickerSubscription>>,
+}
+
+/// 심볼을 구독한다. `Channel` 핸들을 직접 들고 있는 대신, 이 커맨드가 백그라운드
+/// 태스크를 spawn해 큐에서 업데이트를 꺼내 `channel.send`로 전달하는 일을
+/// 전담시킨다. 같은 심볼을 다시 구독하면 기존 태스크는 송신 쪽(`sender`)이
+/// 드롭되어 큐가 닫히면서 자연스럽게 종료된다.
+#[tauri::command]
+fn subscribe_ticker(
+    app_handle: tauri::AppHandle,
+    symbol: String,
+    on_event: tauri::ipc::Channel<TickerUpdate>,
+) -> Result<(), String> {
+    let (sender, mut receiver) = tokio::sync::mpsc::channel::<TickerUpdate>(TICKER_QUEUE_CAPACITY);
+
+    tauri::async_runtime::spawn(async move {
+        while let Some(update) = receiver.recv().await {
+            if on_event.send(update).is_err() {
+                break;
+            }
+        }
+    });
+
+    let state = app_handle.state::<TickerChannelState>();
+    state
+        .channels
+        .lock()
+        .unwrap()
+        .insert(symbol, TickerSubscription { sender });
+    Ok(())
+}
+
+/// 심볼 구독을 해제한다. `unsubscribe_ticker` 커맨드와, 해당 심볼의 티커 창이
+/// 닫힐 때(`WindowEvent::Destroyed`) 둘 다에서 호출되어 채널이 방치되지 않게 한다.
+fn unsubscribe_ticker_channel(app_handle: &tauri::AppHandle, symbol: &str) {
+    let state = app_handle.state::<TickerChannelState>();
+    let subscription = state.channels.lock().unwrap().remove(symbol);
+    if let Some(subscription) = subscription {
+        let _ = subscription.sender.try_send(TickerUpdate::Closed);
+    }
+}
+
+#[tauri::command]
+fn unsubscribe_ticker(app_handle: tauri::AppHandle, symbol: String) -> Result<(), String> {
+    unsubscribe_ticker_channel(&app_handle, &symbol);
     Ok(())
 }
 
@@ -116,7 +804,18 @@ async fn broadcast_ticker_data(
     symbol: String,
     data: serde_json::Value,
 ) -> Result<(), String> {
-    let _ = app_handle.emit(&format!("kis-ticker-data-{}", symbol), data);
+    let sender = {
+        let state = app_handle.state::<TickerChannelState>();
+        state
+            .channels
+            .lock()
+            .unwrap()
+            .get(&symbol)
+            .map(|subscription| subscription.sender.clone())
+    };
+    if let Some(sender) = sender {
+        let _ = sender.send(TickerUpdate::Quote(data)).await;
+    }
     Ok(())
 }
 
@@ -126,7 +825,119 @@ async fn broadcast_ticker_error(
     symbol: String,
     message: String,
 ) -> Result<(), String> {
-    let _ = app_handle.emit(&format!("kis-ticker-error-{}", symbol), message);
+    let sender = {
+        let state = app_handle.state::<TickerChannelState>();
+        state
+            .channels
+            .lock()
+            .unwrap()
+            .get(&symbol)
+            .map(|subscription| subscription.sender.clone())
+    };
+    if let Some(sender) = sender {
+        let _ = sender.send(TickerUpdate::Error(message)).await;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn save_layout(app_handle: tauri::AppHandle, name: String) -> Result<(), String> {
+    let snapshot = current_ticker_layout(&app_handle);
+    let mut file = read_layout_file(&app_handle)?;
+    file.presets.insert(name, snapshot);
+    write_layout_file(&app_handle, &file)
+}
+
+#[tauri::command]
+async fn restore_layout(
+    app_handle: tauri::AppHandle,
+    name: Option<String>,
+) -> Result<(), String> {
+    let file = read_layout_file(&app_handle)?;
+    let entries = match &name {
+        Some(name) => file
+            .presets
+            .get(name)
+            .ok_or_else(|| format!("layout preset '{}' not found", name))?,
+        None => &file.windows,
+    };
+
+    for entry in entries {
+        spawn_ticker_from_layout(&app_handle, entry).await?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn clear_layout(app_handle: tauri::AppHandle, name: String) -> Result<(), String> {
+    let mut file = read_layout_file(&app_handle)?;
+    file.presets.remove(&name);
+    write_layout_file(&app_handle, &file)
+}
+
+#[tauri::command]
+async fn register_shortcut(
+    app_handle: tauri::AppHandle,
+    action: String,
+    accelerator: String,
+) -> Result<(), String> {
+    apply_shortcut_binding(&app_handle, &action, &accelerator)?;
+
+    let mut config = read_shortcut_config(&app_handle)?;
+    config.bindings.insert(action, accelerator);
+    write_shortcut_config(&app_handle, &config)
+}
+
+#[tauri::command]
+async fn update_shortcuts(app_handle: tauri::AppHandle) -> Result<(), String> {
+    load_and_apply_shortcuts(&app_handle)
+}
+
+#[tauri::command]
+fn set_snap_enabled(enabled: bool) -> Result<(), String> {
+    SNAP_ENABLED.store(enabled, Ordering::SeqCst);
+    Ok(())
+}
+
+#[tauri::command]
+fn set_snap_threshold(threshold: f64) -> Result<(), String> {
+    *SNAP_THRESHOLD.lock().unwrap() = threshold;
+    Ok(())
+}
+
+#[tauri::command]
+async fn set_window_opacity(
+    app_handle: tauri::AppHandle,
+    label: String,
+    opacity: f64,
+) -> Result<(), String> {
+    let window = app_handle
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("window '{}' not found", label))?;
+
+    let opacity = opacity.clamp(0.0, 1.0);
+    apply_window_opacity(&window, opacity)?;
+
+    let _ = app_handle.emit("opacity-changed", (label, opacity));
+    Ok(())
+}
+
+#[tauri::command]
+async fn set_global_dim(app_handle: tauri::AppHandle, opacity: f64) -> Result<(), String> {
+    let opacity = opacity.clamp(0.0, 1.0);
+
+    // 모든 라벨이 ticker_로 시작하는 창에 대해 투명도 적용
+    for (label, window) in app_handle.webview_windows() {
+        if label.starts_with("ticker_") {
+            let _ = apply_window_opacity(&window, opacity);
+        }
+    }
+
+    *DEFAULT_OPACITY.lock().unwrap() = opacity;
+    write_opacity_config(&app_handle, &OpacityConfig { opacity })?;
+
+    let _ = app_handle.emit("opacity-changed", ("*".to_string(), opacity));
     Ok(())
 }
 
@@ -136,33 +947,29 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .manage(LayoutState::default())
+        .manage(TickerChannelState::default())
+        .manage(ShortcutState::default())
         .setup(|app| {
-            let ctrl_shift_l = Shortcut::new(
-                Some(
-                    tauri_plugin_global_shortcut::Modifiers::CONTROL
-                        | tauri_plugin_global_shortcut::Modifiers::SHIFT,
-                ),
-                tauri_plugin_global_shortcut::Code::KeyL,
-            );
-
-            app.global_shortcut()
-                .on_shortcut(ctrl_shift_l, move |app, _shortcut, event| {
-                    if event.state() == tauri_plugin_global_shortcut::ShortcutState::Pressed {
-                        let current_lock = IS_LOCKED.load(Ordering::SeqCst);
-                        let new_lock = !current_lock;
-                        IS_LOCKED.store(new_lock, Ordering::SeqCst);
-
-                        // 모든 티커 창에 상태 변경 전파
-                        for (label, window) in app.webview_windows() {
-                            if label.starts_with("ticker_") {
-                                let _ = window.set_ignore_cursor_events(new_lock);
-                            }
-                        }
-
-                        // 프론트엔드에도 알림 (UI 업데이트용)
-                        let _ = app.emit("lock-toggled", new_lock);
+            // 저장된(또는 기본) 단축키 바인딩을 등록한다
+            load_and_apply_shortcuts(app.handle())?;
+
+            // 저장된 기본 투명도를 불러온다. 아래에서 spawn하는 레이아웃 복원 태스크가
+            // spawn_ticker_window_at을 통해 이 값을 바로 읽으므로, 반드시 그 전에
+            // 완료되어야 창들이 복원 즉시 올바른 투명도로 뜬다.
+            if let Ok(config) = read_opacity_config(app.handle()) {
+                *DEFAULT_OPACITY.lock().unwrap() = config.opacity;
+            }
+
+            // 앱 시작 시 마지막으로 저장된 티커 창 배치를 복원한다
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Ok(file) = read_layout_file(&app_handle) {
+                    for entry in &file.windows {
+                        let _ = spawn_ticker_from_layout(&app_handle, entry).await;
                     }
-                })?;
+                }
+            });
 
             // 메인 창(컴트롤 패널) 종료 시 앱 전체 종료
             if let Some(main_window) = app.get_webview_window("main") {
@@ -182,9 +989,21 @@ pub fn run() {
             close_window,
             reset_window_state,
             toggle_lock_from_frontend,
+            set_visible_on_all_workspaces,
             broadcast_border_toggle,
             broadcast_ticker_data,
-            broadcast_ticker_error
+            broadcast_ticker_error,
+            subscribe_ticker,
+            unsubscribe_ticker,
+            save_layout,
+            restore_layout,
+            clear_layout,
+            register_shortcut,
+            update_shortcuts,
+            set_snap_enabled,
+            set_snap_threshold,
+            set_window_opacity,
+            set_global_dim
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");